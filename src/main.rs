@@ -2,15 +2,52 @@
 
 use clap::Parser;
 use eframe::egui::*;
+use serde::{Deserialize, Serialize};
 
 const PEAK_HOLD_TIME: usize = 4000;
 const DECAY_FACTOR: f32 = 0.9999;
+/// Integration time constant for the VU-style RMS readout, in seconds.
+const VU_TIME_CONSTANT: f32 = 0.3;
+/// Floor/ceiling of the meter's dB scale and the fixed tick marks drawn on it.
+const METER_FLOOR_DB: f32 = -60.0;
+const METER_CEIL_DB: f32 = 6.0;
+const METER_TICKS_DB: [f32; 8] = [-60.0, -48.0, -36.0, -24.0, -12.0, -6.0, 0.0, 6.0];
+/// Size, in seconds of audio, of each chunk the record buffer grows by: reserved
+/// when recording starts, then again by `grow_record_buffer_ahead` as it fills.
+const RECORD_BUFFER_CHUNK_SECONDS: f32 = 300.0;
+/// How far ahead of the current write position to keep the record buffer's
+/// capacity topped up, so the GUI thread's top-up (see
+/// `grow_record_buffer_ahead`) always wins the race against the JACK process
+/// callback's push before it runs out of reserved room.
+const RECORD_BUFFER_GROW_AHEAD_SECONDS: f32 = 30.0;
+
+const ANALYZER_FFT_SIZE: usize = 2048;
+const ANALYZER_DECAY: f32 = 0.7;
 
 use std::{process::exit, sync::{Arc, Mutex}};
+use std::collections::HashMap;
+use std::net::UdpSocket;
 
 fn main() -> eframe::Result {
 	let args = Args::parse();
-	let shared_mix = Arc::new(Mutex::new(FslcMix::new(args.channels)));
+	let mut mix = FslcMix::new(args.channels);
+	if let Some(session_path) = &args.session {
+		mix.session_path = session_path.clone();
+		if let Err(err) = mix.load_session(std::path::Path::new(session_path)) {
+			eprintln!("Could not load session {session_path}: {err}");
+		}
+	}
+	let shared_mix = Arc::new(Mutex::new(mix));
+	if let Some(osc_port) = args.osc_port {
+		match start_osc_server(osc_port, shared_mix.clone()) {
+			Ok(feedback) => {
+				shared_mix.lock().unwrap().osc = Some(feedback);
+			}
+			Err(err) => {
+				eprintln!("Could not start OSC server on port {osc_port}: {err}");
+			}
+		}
+	}
 	let app = MixApp {
 		mix : shared_mix.clone(),
 	};
@@ -23,10 +60,10 @@ fn main() -> eframe::Result {
 			..Default::default()
 		};
 
-		let process_callback = register_jack_callback(&client, shared_mix);
+		let (process_callback, notifier) = register_jack_callback(&client, shared_mix);
 		// Create process and activate the client
 		let process = jack::contrib::ClosureProcessHandler::new(process_callback);
-		let active_client = client.activate_async((), process).unwrap();
+		let active_client = client.activate_async(notifier, process).unwrap();
 		let result = eframe::run_native(
 			"FSLCMix", 
 			options,
@@ -64,25 +101,83 @@ fn main() -> eframe::Result {
 	}
 }
 
-fn register_jack_callback(client: &jack::Client, mixer: Arc<Mutex<FslcMix>>) -> impl FnMut(&jack::Client, &jack::ProcessScope) -> jack::Control  {
-	let unlocked_mixer = mixer.lock().unwrap();
-	let in_ports = unlocked_mixer.channels.iter().map(
-		|channel| channel.declare_jack_port(&client)).collect::<Vec<_>>();
-	let mut out_port = client.register_port("Master Out", jack::AudioOut::default()).unwrap();
+fn register_jack_callback(client: &jack::Client, mixer: Arc<Mutex<FslcMix>>) -> (impl FnMut(&jack::Client, &jack::ProcessScope) -> jack::Control, PortConnectionNotifier)  {
+	let mut unlocked_mixer = mixer.lock().unwrap();
+	let mut channel_ports = HashMap::new();
+	let in_ports = unlocked_mixer.channels.iter_mut().enumerate().map(|(channel_index, channel)| {
+		let port = channel.declare_jack_port(&client);
+		// Reconnect to a restored session's saved source, if any.
+		if !channel.external_source.is_empty() {
+			if let Ok(port_name) = port.name() {
+				if let Err(err) = client.connect_ports_by_name(&channel.external_source, &port_name) {
+					eprintln!("Could not reconnect {} to {}: {err}", channel.external_source, port_name);
+				}
+			}
+		}
+		// Record whatever the port actually ends up connected to (our own
+		// reconnect above, or a connection already made outside fslcmix) so
+		// a later session save reflects the real patch, not stale text.
+		if let Some(source) = port.connections().first() {
+			channel.external_source = source.clone();
+		}
+		channel_ports.insert(port.id(), channel_index);
+		port
+	}).collect::<Vec<_>>();
+	drop(unlocked_mixer);
+	let notifier = PortConnectionNotifier {
+		mixer: Arc::clone(&mixer),
+		channel_ports,
+	};
+	let mut out_port_l = client.register_port("Master Out L", jack::AudioOut::default()).unwrap();
+	let mut out_port_r = client.register_port("Master Out R", jack::AudioOut::default()).unwrap();
 	let process_callback = {
 		let mixer = Arc::clone(&mixer);
 		move |_: &jack::Client, ps: &jack::ProcessScope| -> jack::Control {
 			let ins = in_ports.iter().map(|port| port.as_slice(ps)).collect::<Vec<_>>();
-			let out = out_port.as_mut_slice(ps);
+			let out_l = out_port_l.as_mut_slice(ps);
+			let out_r = out_port_r.as_mut_slice(ps);
+			let sample_rate = ps.sample_rate() as f32;
 			if let Ok(mut owned_mixer) = mixer.lock() {
-				owned_mixer.mix(ins, out);
+				owned_mixer.mix(ins, out_l, out_r, sample_rate);
 			} else {
 				eprintln!("Could not gain access to mutex!");
 			}
 			jack::Control::Continue
 		}
 	};
-	process_callback
+	(process_callback, notifier)
+}
+
+/// Keeps each channel's `external_source` in sync with whatever it's really
+/// patched to, by reacting to JACK's own connect/disconnect notifications
+/// instead of only checking once at startup. Runs on JACK's notification
+/// thread, not the realtime process thread, so locking the mixer here is safe.
+struct PortConnectionNotifier {
+	mixer: Arc<Mutex<FslcMix>>,
+	channel_ports: HashMap<jack::PortId, usize>,
+}
+
+impl jack::NotificationHandler for PortConnectionNotifier {
+	fn ports_connected(&mut self, client: &jack::Client, port_id_a: jack::PortId, port_id_b: jack::PortId, are_connected: bool) {
+		let (channel_port_id, other_port_id) = if self.channel_ports.contains_key(&port_id_a) {
+			(port_id_a, port_id_b)
+		} else if self.channel_ports.contains_key(&port_id_b) {
+			(port_id_b, port_id_a)
+		} else {
+			return;
+		};
+		let Some(&channel_index) = self.channel_ports.get(&channel_port_id) else { return; };
+		let new_source = if are_connected {
+			client.port_by_id(other_port_id).and_then(|port| port.name().ok()).unwrap_or_default()
+		} else {
+			String::new()
+		};
+		if let Ok(mut mixer) = self.mixer.lock() {
+			if let Some(channel) = mixer.channels.get_mut(channel_index) {
+				channel.external_source = new_source;
+			}
+		}
+	}
 }
 
 fn db_peak(val : f32) -> f32 {
@@ -90,7 +185,119 @@ fn db_peak(val : f32) -> f32 {
 }
 
 fn db_rms(val : f32) -> f32 {
-	10.0 * val.log10()
+	// val is an RMS amplitude (sqrt of mean square), same domain as db_peak's
+	// input, so it uses the same 20x coefficient rather than a power's 10x.
+	20.0 * val.log10()
+}
+
+/// Maps a dB value onto [0, 1] across the meter's floor/ceiling range.
+fn db_to_unit(db : f32) -> f32 {
+	((db - METER_FLOOR_DB) / (METER_CEIL_DB - METER_FLOOR_DB)).clamp(0.0, 1.0)
+}
+
+fn apply_gain_and_limit(sample : f32, gain : f32, limit : bool) -> f32 {
+	let gained = sample * gain;
+	if limit && gained >= 1.0 {
+		1.0
+	} else if limit && gained <= -1.0 {
+		-1.0
+	} else {
+		gained
+	}
+}
+
+/// Computes (peak absolute value, sum of squares) over `input`, using a
+/// 4-wide SSE fast path on x86_64 when the CPU supports it at runtime, and
+/// falling back to the plain scalar loop otherwise.
+fn peak_and_sum_sq(input: &[f32]) -> (f32, f32) {
+	#[cfg(target_arch = "x86_64")]
+	{
+		if is_x86_feature_detected!("sse") {
+			return unsafe { peak_and_sum_sq_sse(input) };
+		}
+	}
+	peak_and_sum_sq_scalar(input)
+}
+
+fn peak_and_sum_sq_scalar(input: &[f32]) -> (f32, f32) {
+	let mut peak = 0.0f32;
+	let mut sum_sq = 0.0f32;
+	for &x in input {
+		let abs_x = x.abs();
+		if abs_x > peak {
+			peak = abs_x;
+		}
+		sum_sq += x * x;
+	}
+	(peak, sum_sq)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse")]
+unsafe fn peak_and_sum_sq_sse(input: &[f32]) -> (f32, f32) {
+	use std::arch::x86_64::*;
+
+	let mut peak_vec = _mm_setzero_ps();
+	let mut sumsq_vec = _mm_setzero_ps();
+	let abs_mask = _mm_set1_ps(-0.0f32);
+	let chunks = input.chunks_exact(4);
+	let remainder = chunks.remainder();
+	for chunk in chunks {
+		let v = _mm_loadu_ps(chunk.as_ptr());
+		let abs_v = _mm_andnot_ps(abs_mask, v);
+		peak_vec = _mm_max_ps(peak_vec, abs_v);
+		sumsq_vec = _mm_add_ps(sumsq_vec, _mm_mul_ps(v, v));
+	}
+	// Horizontal reduction: shuffle+max for the peak, shuffle+add for the sum.
+	let shuf = _mm_shuffle_ps(peak_vec, peak_vec, 0b10_11_00_01);
+	let max1 = _mm_max_ps(peak_vec, shuf);
+	let max2 = _mm_max_ps(max1, _mm_movehl_ps(max1, max1));
+	let mut peak = _mm_cvtss_f32(max2);
+
+	let shuf = _mm_shuffle_ps(sumsq_vec, sumsq_vec, 0b10_11_00_01);
+	let sum1 = _mm_add_ps(sumsq_vec, shuf);
+	let sum2 = _mm_add_ps(sum1, _mm_movehl_ps(sum1, sum1));
+	let mut sum_sq = _mm_cvtss_f32(sum2);
+
+	for &x in remainder {
+		let abs_x = x.abs();
+		if abs_x > peak {
+			peak = abs_x;
+		}
+		sum_sq += x * x;
+	}
+	(peak, sum_sq)
+}
+
+/// Writes a mono 32-bit float PCM WAV file.
+fn write_wav_f32(path: &std::path::Path, samples: &[f32], sample_rate: u32) -> std::io::Result<()> {
+	use std::io::Write;
+	let channels: u16 = 1;
+	let bits_per_sample: u16 = 32;
+	let byte_rate = sample_rate * channels as u32 * bits_per_sample as u32 / 8;
+	let block_align = channels * bits_per_sample / 8;
+	let data_len = (samples.len() * 4) as u32;
+	let fmt_len: u32 = 16;
+	let riff_len = 4 + (8 + fmt_len) + (8 + data_len);
+
+	let mut file = std::fs::File::create(path)?;
+	file.write_all(b"RIFF")?;
+	file.write_all(&riff_len.to_le_bytes())?;
+	file.write_all(b"WAVE")?;
+	file.write_all(b"fmt ")?;
+	file.write_all(&fmt_len.to_le_bytes())?;
+	file.write_all(&3u16.to_le_bytes())?; // IEEE float format
+	file.write_all(&channels.to_le_bytes())?;
+	file.write_all(&sample_rate.to_le_bytes())?;
+	file.write_all(&byte_rate.to_le_bytes())?;
+	file.write_all(&block_align.to_le_bytes())?;
+	file.write_all(&bits_per_sample.to_le_bytes())?;
+	file.write_all(b"data")?;
+	file.write_all(&data_len.to_le_bytes())?;
+	for &sample in samples {
+		file.write_all(&sample.to_le_bytes())?;
+	}
+	Ok(())
 }
 
 struct ErrorBox {
@@ -116,10 +323,42 @@ struct MixApp {
 	mix: Arc<Mutex<FslcMix>>,
 }
 
+/// File I/O that a GUI frame wants to perform, collected while the mixer's
+/// lock is held and carried out only after it's dropped, so a slow disk
+/// write can never stall the JACK process callback waiting on the same lock.
+#[derive(Default)]
+struct PendingIo {
+	save: Option<(String, Session)>,
+	load: Option<String>,
+	export: Vec<(String, Vec<f32>, u32)>,
+}
+
 impl eframe::App for MixApp {
 	fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
-		let mut owned_mix = self.mix.lock().unwrap();
-		owned_mix.update(ctx, frame);
+		let mut pending = PendingIo::default();
+		{
+			let mut owned_mix = self.mix.lock().unwrap();
+			owned_mix.update(ctx, frame, &mut pending);
+		}
+		if let Some((path, session)) = pending.save {
+			if let Err(err) = write_session(std::path::Path::new(&path), &session) {
+				eprintln!("Could not save session {path}: {err}");
+			}
+		}
+		if let Some(path) = pending.load {
+			match read_session(std::path::Path::new(&path)) {
+				Ok(session) => {
+					let mut owned_mix = self.mix.lock().unwrap();
+					owned_mix.apply_session(session);
+				}
+				Err(err) => eprintln!("Could not load session {path}: {err}"),
+			}
+		}
+		for (file_name, samples, sample_rate) in pending.export {
+			if let Err(err) = write_wav_f32(std::path::Path::new(&file_name), &samples, sample_rate) {
+				eprintln!("Could not export {file_name}: {err}");
+			}
+		}
 	}
 }
 
@@ -130,6 +369,14 @@ struct Args {
 	/// Number of channels
 	#[arg(short, long, default_value_t = 5)]
 	channels: u8,
+
+	/// UDP port to listen on for OSC remote control (disabled if unset)
+	#[arg(long)]
+	osc_port: Option<u16>,
+
+	/// Session file to load on startup
+	#[arg(long)]
+	session: Option<String>,
 }
 
 struct FslcMix {
@@ -138,6 +385,14 @@ struct FslcMix {
 	normalize: bool,
 	ui_size: egui::Vec2,
 	max_gain: f32,
+	analyzer: Analyzer,
+	sample_rate: f32,
+	osc: Option<OscFeedback>,
+	session_path: String,
+	/// Scratch space for the post-gain L/R average fed to the master's RMS
+	/// ballistics, reused across calls like `MixChannel::scratch` so the
+	/// JACK process callback never allocates.
+	master_mid: Vec<f32>,
 }
 
 impl FslcMix {
@@ -157,15 +412,21 @@ impl FslcMix {
 			ui_size: egui::Vec2::new(400.0, 330.0), // This size doesn't matter since it's
 													// overritten
 			max_gain: 1.25,
+			analyzer: Analyzer::new(),
+			sample_rate: 48000.0,
+			osc: None,
+			session_path: "session.json".to_owned(),
+			master_mid: Vec::new(),
 		}
 	}
 
-	fn mix(&mut self, inputs : Vec<&[f32]>, output : &mut [f32]) {
+	fn mix(&mut self, inputs : Vec<&[f32]>, output_l : &mut [f32], output_r : &mut [f32], sample_rate : f32) {
 		// Sanity check
 		assert!(inputs.len() == self.channels.len());
 		// Initialize to zeros
-		for i in 0..output.len() {
-			output[i] = 0.0;
+		for i in 0..output_l.len() {
+			output_l[i] = 0.0;
+			output_r[i] = 0.0;
 		}
 		if self.master.mute {
 			return;
@@ -175,39 +436,45 @@ impl FslcMix {
 			.fold(false, |any_so_far, channel| {
 				channel.solo || any_so_far
 			});
-		// Mix each channel together
+		// Mix each channel together, panned into the stereo field
 		for channel_index in 0..inputs.len() {
 			let channel = &mut self.channels[channel_index];
 			let input = inputs[channel_index];
-			channel.mix(input, output, any_solo);
+			channel.mix(input, output_l, output_r, any_solo, sample_rate);
 		}
 		// Apply the master channel's mix and normalize if necessary
 		let norm_factor = inputs.len() as f32;
 		// Bypass its mix() function since we do it slightly different here
-		self.master.rms(output);
-		for i in 0..output.len() {
+		self.master_mid.clear();
+		for i in 0..output_l.len() {
 			if self.normalize {
-				output[i] /= norm_factor;
+				output_l[i] /= norm_factor;
+				output_r[i] /= norm_factor;
 			}
-			let sample = output[i] * self.master.gain;
-			let out_sample = if self.master.limit && sample >= 1.0 {
-				1.0
-			} else if self.master.limit && sample <= -1.0 {
-				-1.0
-			} else {
-				sample
-			};
-			if out_sample > self.master.max {
-				self.master.max = out_sample;
+			let out_l = apply_gain_and_limit(output_l[i], self.master.gain, self.master.limit);
+			let out_r = apply_gain_and_limit(output_r[i], self.master.gain, self.master.limit);
+			if out_l > self.master.max {
+				self.master.max = out_l;
 			}
-			output[i] = out_sample;
+			if out_r > self.master.max {
+				self.master.max = out_r;
+			}
+			output_l[i] = out_l;
+			output_r[i] = out_r;
 
-			self.master.update_smoothed(out_sample);
+			let mid = (out_l + out_r) * 0.5;
+			self.master.update_smoothed(mid);
+			self.master_mid.push(mid);
+			self.analyzer.push_sample(mid);
 		}
-		self.master.last = output[output.len() - 1];
+		// RMS and peak ballistics are metered on the L/R average, like the analyzer,
+		// so a signal panned hard to one side still registers on the master meter.
+		self.master.rms(&self.master_mid, sample_rate);
+		self.master.last = output_l[output_l.len() - 1];
+		self.sample_rate = sample_rate;
 	}
 
-	fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+	fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame, pending: &mut PendingIo) {
 	   //egui::Window::new("Mixer (FSLCMix)")
 		//	.default_pos([100.0, 100.0])
 		//	.title_bar(true)
@@ -236,20 +503,38 @@ impl FslcMix {
 							channel.solo = false;
 						}
 					}
+					ui.separator();
+					ui.add(egui::TextEdit::singleline(&mut self.session_path).desired_width(120.0));
+					if ui.button("Save Session").clicked() {
+						// Snapshot now, under the lock; the actual write happens
+						// after MixApp::update releases it, off the audio thread's path.
+						pending.save = Some((self.session_path.clone(), self.snapshot_session()));
+					}
+					if ui.button("Load Session").clicked() {
+						pending.load = Some(self.session_path.clone());
+					}
 				});
 			});
 			ui.horizontal(|ui| {
-				self.master.ui(ui);
+				self.master.ui(ui, &mut pending.export);
 				ui.separator();
 				egui::ScrollArea::horizontal().show(ui, |ui| {
 					for channel in &mut self.channels {
-						channel.ui(ui);
+						channel.ui(ui, &mut pending.export);
 					}
 				});
 			});
+			egui::CollapsingHeader::new("Analyzer")
+				.default_open(false)
+				.show(ui, |ui| {
+					// Only run the FFT while the panel is actually expanded.
+					self.analyzer.update(self.sample_rate);
+					self.analyzer.ui(ui);
+				});
 			self.ui_size = ctx.used_size();
 			// let window_size = self.ui_size + egui::vec2(20.0, 40.0);
 		});
+		self.sync_osc_feedback();
 		ctx.request_repaint();
 		// frame.set_window_size(window_size);
 		// frame.request_repaint();
@@ -263,6 +548,59 @@ impl FslcMix {
 		self.master.max_gain = self.max_gain;
 
 	}
+
+	/// Sends any parameters that changed since the last call (or a full
+	/// resync if the channel count changed) back out to registered OSC
+	/// clients, so motorized faders stay in sync with the GUI.
+	fn sync_osc_feedback(&mut self) {
+		let Some(mut osc) = self.osc.take() else { return; };
+		osc.sync(&self.channels, &self.master, self.normalize);
+		self.osc = Some(osc);
+	}
+
+	/// Builds the persisted state without touching disk; the caller decides
+	/// when and where the actual write happens.
+	fn snapshot_session(&self) -> Session {
+		Session {
+			channels: self.channels.iter().map(SessionChannel::capture).collect(),
+			master: SessionChannel::capture(&self.master),
+			normalize: self.normalize,
+			max_gain: self.max_gain,
+		}
+	}
+
+	/// Restores state from an already-parsed [`Session`]; the caller is
+	/// responsible for any disk I/O that produced it.
+	fn apply_session(&mut self, session: Session) {
+		self.channels = session.channels.iter().map(SessionChannel::restore).collect();
+		self.master = session.master.restore();
+		self.normalize = session.normalize;
+		self.max_gain = session.max_gain;
+		self.update_max_gain();
+	}
+
+	/// Synchronous save, only safe to call before the mixer is shared with
+	/// the JACK callback (e.g. at startup) since it blocks on disk I/O.
+	fn save_session(&self, path: &std::path::Path) -> std::io::Result<()> {
+		write_session(path, &self.snapshot_session())
+	}
+
+	/// Synchronous load, only safe to call before the mixer is shared with
+	/// the JACK callback (e.g. at startup) since it blocks on disk I/O.
+	fn load_session(&mut self, path: &std::path::Path) -> std::io::Result<()> {
+		self.apply_session(read_session(path)?);
+		Ok(())
+	}
+}
+
+fn write_session(path: &std::path::Path, session: &Session) -> std::io::Result<()> {
+	let file = std::fs::File::create(path)?;
+	serde_json::to_writer_pretty(file, session).map_err(std::io::Error::other)
+}
+
+fn read_session(path: &std::path::Path) -> std::io::Result<Session> {
+	let file = std::fs::File::open(path)?;
+	serde_json::from_reader(file).map_err(std::io::Error::other)
 }
 
 struct MixChannel {
@@ -272,6 +610,8 @@ struct MixChannel {
 	peak_hold_counter: usize,
 	max: f32,
 	last_rms: f32,
+	/// Running mean-square feeding the VU-style ballistics in `rms()`.
+	vu_mean_sq: f32,
 	channel_name: String,
 	limit: bool,
 	mute: bool,
@@ -279,41 +619,70 @@ struct MixChannel {
 	others_solo: bool,
 	show_rms: bool,
 	max_gain: f32,
+	eq: [EqBand; 4],
+	pan: f32,
+	/// JACK source port this channel's input is connected to. It is reconnected
+	/// when the JACK ports are registered at startup (e.g. after `--session`);
+	/// loading a session from the GUI does not reconnect ports.
+	external_source: String,
+	recording: bool,
+	record_buffer: Vec<f32>,
+	last_sample_rate: f32,
+	/// Scratch space for the per-buffer processed signal, reused across
+	/// calls so the SIMD peak scan has something contiguous to read.
+	scratch: Vec<f32>,
 }
 
 impl MixChannel {
 
-	fn mix(&mut self, input : &[f32], output : &mut [f32], any_solo : bool) {
+	fn mix(&mut self, input : &[f32], output_l : &mut [f32], output_r : &mut [f32], any_solo : bool, sample_rate : f32) {
 		// if self.mute || (any_solo && !self.solo) {
 		// 	self.last = 0.0;
 		// 	return;
 		// }
 		self.others_solo = any_solo;
 		// Sanity check
-		assert!(input.len() == output.len());
-		self.rms(input);
+		assert!(input.len() == output_l.len() && input.len() == output_r.len());
+		self.last_sample_rate = sample_rate;
+		self.rms(input, sample_rate);
+		for band in &mut self.eq {
+			band.recompute_coeffs(sample_rate);
+		}
+		// Constant-power pan law, computed once per buffer like the EQ coefficients.
+		let theta = (self.pan + 1.0) * std::f32::consts::FRAC_PI_4;
+		let pan_l = theta.cos();
+		let pan_r = theta.sin();
+		self.scratch.clear();
 		for i in 0..input.len() {
-			let sample = input[i] * self.gain;
-			let out_sample = if self.limit && sample >= 1.0 {
-				1.0
-			} else if self.limit && sample <= -1.0 {
-				-1.0
-			} else {
-				sample
-			};
-			if out_sample > self.max {
-				self.max = out_sample;
+			let mut eq_sample = input[i];
+			for band in &mut self.eq {
+				eq_sample = band.process(eq_sample);
 			}
+			let out_sample = apply_gain_and_limit(eq_sample, self.gain, self.limit);
+			self.scratch.push(out_sample);
 			// Only mix into the output if we're not muted or no other tracks have solo
 			if !(self.mute || (any_solo && !self.solo)) {
-				output[i] += out_sample;
+				output_l[i] += out_sample * pan_l;
+				output_r[i] += out_sample * pan_r;
+			}
+			if self.recording {
+				self.record_buffer.push(out_sample);
 			}
 			self.last = out_sample;
 			self.update_smoothed(self.last);
 		}
+		// Batch the peak scan over the buffer we just built instead of
+		// branching on every sample above.
+		let (peak, _) = peak_and_sum_sq(&self.scratch);
+		if peak > self.max {
+			self.max = peak;
+		}
 	}
 
-	fn ui(&mut self, ui : &mut egui::Ui) {
+	fn ui(&mut self, ui : &mut egui::Ui, pending_export: &mut Vec<(String, Vec<f32>, u32)>) {
+		if self.recording {
+			self.grow_record_buffer_ahead();
+		}
 		ui.vertical(|ui| {
 			ui.vertical(|ui| {
 				let wrap_mode = TextWrapMode::Extend;
@@ -332,6 +701,7 @@ impl MixChannel {
 					.wrap_mode(wrap_mode));
 				if rb.clicked() {
 					self.last_rms = 0.0;
+					self.vu_mean_sq = 0.0;
 				}
 			});
 			ui.horizontal(|ui| {
@@ -351,73 +721,134 @@ impl MixChannel {
 				}
 				ui.toggle_value(&mut self.show_rms, "RMS");
 			});
+			ui.horizontal(|ui| {
+				ui.label("Pan");
+				ui.add(egui::Slider::new(&mut self.pan, -1.0..=1.0).show_value(false));
+				let btn = ui.button("C");
+				if btn.clicked() {
+					self.pan = 0.0;
+				}
+			});
 			ui.horizontal(|ui| {
 				ui.toggle_value(&mut self.mute, "M");
 				ui.toggle_value(&mut self.solo, "S");
 				ui.toggle_value(&mut self.limit, "Lim");
 			});
 			ui.add(egui::TextEdit::singleline(&mut self.channel_name).desired_width(85.0));
+			egui::CollapsingHeader::new("EQ")
+				.id_salt(&self.channel_name)
+				.default_open(false)
+				.show(ui, |ui| {
+					for band in &mut self.eq {
+						band.ui(ui);
+					}
+				});
+			egui::CollapsingHeader::new("Export")
+				.id_salt(format!("{}-export", self.channel_name))
+				.default_open(false)
+				.show(ui, |ui| {
+					ui.horizontal(|ui| {
+						ui.label("Source");
+						ui.add(egui::TextEdit::singleline(&mut self.external_source).desired_width(85.0));
+					});
+					let rec_label = if self.recording { "Stop" } else { "Rec" };
+					if ui.button(rec_label).clicked() {
+						if self.recording {
+							pending_export.push(self.take_export());
+						} else {
+							self.record_buffer.clear();
+							self.record_buffer.reserve(self.record_chunk_samples());
+						}
+						self.recording = !self.recording;
+					}
+					if self.recording {
+						ui.label(format!("{} samples", self.record_buffer.len()));
+					}
+				});
 		});
 	}
 
+	/// Hands off the channel's recorded post-gain/post-EQ audio for writing
+	/// and clears the record buffer; the caller does the actual disk I/O
+	/// once the mixer's lock is no longer held by the JACK callback's path.
+	fn take_export(&mut self) -> (String, Vec<f32>, u32) {
+		let file_name = format!("{}.wav", self.channel_name.replace(' ', "_"));
+		let samples = std::mem::take(&mut self.record_buffer);
+		(file_name, samples, self.last_sample_rate as u32)
+	}
+
+	fn record_chunk_samples(&self) -> usize {
+		(self.last_sample_rate * RECORD_BUFFER_CHUNK_SECONDS) as usize
+	}
+
+	/// Tops up the record buffer's capacity a chunk at a time, from the GUI
+	/// thread, well before the JACK process callback's per-sample push would
+	/// otherwise hit the end of the reserved room and realloc on the
+	/// realtime thread. Called every frame while recording, not just when
+	/// the Export panel is expanded.
+	fn grow_record_buffer_ahead(&mut self) {
+		let grow_ahead_samples = (self.last_sample_rate * RECORD_BUFFER_GROW_AHEAD_SECONDS) as usize;
+		let remaining = self.record_buffer.capacity() - self.record_buffer.len();
+		if remaining < grow_ahead_samples {
+			self.record_buffer.reserve(self.record_chunk_samples());
+		}
+	}
+
 	fn levels_bar(&self, ui: &mut Ui) {
-		// TODO: log scale so dB looks nice
-		let val = if self.show_rms { 
-			self.last_rms 
+		// Fast-attack/slow-release peak ballistics come from update_smoothed(); the
+		// RMS readout is VU-style integrated in rms(). Both land here as dB so the
+		// bar fills linearly in dB rather than linearly in amplitude.
+		let val = if self.show_rms {
+			self.last_rms
 		} else {
 			self.last_smoothed
 		};
 		let val_db = if self.show_rms {
-			db_rms(val)
+			db_rms(val.max(1e-6))
 		} else {
-			db_peak(val)
+			db_peak(val.max(1e-6))
 		};
-		let (rect, response) = ui.allocate_exact_size(vec2(10.0, 190.0), egui::Sense::hover()); 
-		let painter = ui.painter(); 
-		let filled_height = (rect.height() * val / self.max_gain).min(rect.height()); // Show a bit over max amplitude 
-		// let filled_rect = Rect::from_min_max(rect.min, rect.min + vec2(rect.width(), filled_height)); 
-		// let remaining_rect = Rect::from_min_max(filled_rect.max, rect.max);
+		let (rect, response) = ui.allocate_exact_size(vec2(10.0, 190.0), egui::Sense::hover());
+		let painter = ui.painter();
+		let filled_height = rect.height() * db_to_unit(val_db);
 		let filled_rect = Rect::from_min_max(rect.max - vec2(rect.width(), filled_height), rect.max);
-		// let remaining_rect = Rect::from_min_max(rect.min, filled_rect.max);
-		// painter.rect_filled(remaining_rect, 0.0, Color32::from_rgb(200, 0, 0));
 		let color_saturation = if self.mute || (!self.solo && self.others_solo) { 50 } else { 200 };
-		let color = if val < 1.0 { 
-			Color32::from_rgb(0, color_saturation, 0) 
-		} else if val < self.max_gain {
+		let color = if val_db < -6.0 {
+			Color32::from_rgb(0, color_saturation, 0)
+		} else if val_db < 0.0 {
 			Color32::from_rgb(color_saturation, color_saturation, 0)
-		} else { 
-			Color32::from_rgb(color_saturation, 0, 0) 
+		} else {
+			Color32::from_rgb(color_saturation, 0, 0)
 		};
-		painter.rect_filled(filled_rect, 0.0, color); 
+		painter.rect_filled(filled_rect, 0.0, color);
 		painter.rect_stroke(rect, 0.0, (1.0, Color32::DARK_GRAY));
-		// Draw scale numbers 
-		let num_steps = (self.max_gain * 10.0) as u16;
-		let step_size = rect.height() / num_steps as f32; 
-		for i in 0..=num_steps { 
-			let y_pos = rect.top() + i as f32 * step_size; 
-			let number = if self.show_rms { 
-				db_rms((num_steps - i) as f32 / 10.0)
-			} else { 
-				db_peak((num_steps - i) as f32 / 10.0)
-			}; 
-			// Invert the order if you want 0 at the bottom 
+		// Draw scale numbers at fixed dB steps so the gradations are evenly spaced.
+		for &tick_db in &METER_TICKS_DB {
+			let y_pos = rect.bottom() - db_to_unit(tick_db) * rect.height();
 			let text_pos = Pos2::new(rect.right() + 5.0, y_pos);
-			painter.text(text_pos, 
-				Align2::LEFT_CENTER, 
-				format!("{:.1}", number), 
+			painter.text(text_pos,
+				Align2::LEFT_CENTER,
+				format!("{:.0}", tick_db),
 				FontId::new(9.0, FontFamily::Monospace),
 				Color32::DARK_GRAY);
 		}
-		response.on_hover_cursor(egui::CursorIcon::PointingHand) 
-			.on_hover_text(format!("{:.3} dB", val_db)); 
+		response.on_hover_cursor(egui::CursorIcon::PointingHand)
+			.on_hover_text(format!("{:.3} dB", val_db));
 	}
 
 	fn declare_jack_port(&self, client : &jack::Client) -> jack::Port<jack::AudioIn> {
 		client.register_port(&self.channel_name, jack::AudioIn::default()).unwrap()
 	}
 
-	fn rms(&mut self, input : &[f32]) {
-		self.last_rms = (input.iter().map(|x| x * x).sum::<f32>() / input.len() as f32).sqrt();
+	/// VU-style RMS with a ~300ms integration time constant, rather than the
+	/// instantaneous per-buffer RMS, so the meter doesn't flicker with every block.
+	fn rms(&mut self, input : &[f32], sample_rate : f32) {
+		let (_, sum_sq) = peak_and_sum_sq(input);
+		let block_mean_sq = sum_sq / input.len() as f32;
+		let dt = input.len() as f32 / sample_rate.max(1.0);
+		let alpha = (dt / (VU_TIME_CONSTANT + dt)).clamp(0.0, 1.0);
+		self.vu_mean_sq += alpha * (block_mean_sq - self.vu_mean_sq);
+		self.last_rms = self.vu_mean_sq.sqrt();
 	}
 
 	fn update_smoothed(&mut self, peak : f32) {
@@ -441,6 +872,7 @@ impl Default for MixChannel {
 			peak_hold_counter: 0,
 			max: 0.0,
 			last_rms: 0.0,
+			vu_mean_sq: 0.0,
 			channel_name: "Channel".to_owned(),
 			limit: false,
 			mute: false,
@@ -448,6 +880,634 @@ impl Default for MixChannel {
 			others_solo: false,
 			show_rms: false,
 			max_gain: 1.25,
+			eq: [
+				EqBand::new(EqBandKind::LowShelf, 100.0, 0.0, 0.707),
+				EqBand::new(EqBandKind::Peak, 500.0, 0.0, 0.707),
+				EqBand::new(EqBandKind::Peak, 2000.0, 0.0, 0.707),
+				EqBand::new(EqBandKind::HighShelf, 8000.0, 0.0, 0.707),
+			],
+			pan: 0.0,
+			external_source: String::new(),
+			recording: false,
+			record_buffer: Vec::new(),
+			last_sample_rate: 48000.0,
+			scratch: Vec::new(),
+		}
+	}
+}
+
+/// Persisted state of a single EQ band: just the knobs, not the runtime
+/// filter state or coefficients.
+#[derive(Serialize, Deserialize)]
+struct SessionEqBand {
+	freq: f32,
+	gain_db: f32,
+	q: f32,
+}
+
+/// Persisted state of one channel strip (also used for the master strip).
+#[derive(Serialize, Deserialize)]
+struct SessionChannel {
+	name: String,
+	gain: f32,
+	pan: f32,
+	mute: bool,
+	solo: bool,
+	limit: bool,
+	external_source: String,
+	eq: [SessionEqBand; 4],
+}
+
+impl SessionChannel {
+	fn capture(channel: &MixChannel) -> Self {
+		Self {
+			name: channel.channel_name.clone(),
+			gain: channel.gain,
+			pan: channel.pan,
+			mute: channel.mute,
+			solo: channel.solo,
+			limit: channel.limit,
+			external_source: channel.external_source.clone(),
+			eq: std::array::from_fn(|i| SessionEqBand {
+				freq: channel.eq[i].freq,
+				gain_db: channel.eq[i].gain_db,
+				q: channel.eq[i].q,
+			}),
+		}
+	}
+
+	fn restore(&self) -> MixChannel {
+		let mut channel = MixChannel {
+			channel_name: self.name.clone(),
+			gain: self.gain,
+			pan: self.pan,
+			mute: self.mute,
+			solo: self.solo,
+			limit: self.limit,
+			external_source: self.external_source.clone(),
+			..Default::default()
+		};
+		for (band, saved) in channel.eq.iter_mut().zip(self.eq.iter()) {
+			band.freq = saved.freq;
+			band.gain_db = saved.gain_db;
+			band.q = saved.q;
+			band.dirty = true;
+		}
+		channel
+	}
+}
+
+/// Full persisted mixer state, written/read by [`FslcMix::save_session`]
+/// and [`FslcMix::load_session`].
+#[derive(Serialize, Deserialize)]
+struct Session {
+	channels: Vec<SessionChannel>,
+	master: SessionChannel,
+	normalize: bool,
+	max_gain: f32,
+}
+
+/// The shape of a single [`EqBand`], following the classic four-band
+/// channel-strip layout: shelves on the outside, peaking bells in the
+/// middle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum EqBandKind {
+	LowShelf,
+	Peak,
+	HighShelf,
+}
+
+impl EqBandKind {
+	fn label(&self) -> &'static str {
+		match self {
+			EqBandKind::LowShelf => "Low",
+			EqBandKind::Peak => "Mid",
+			EqBandKind::HighShelf => "High",
 		}
 	}
 }
+
+/// A single RBJ-cookbook biquad, run in Direct Form I. Coefficients are
+/// recomputed lazily whenever `freq`, `gain_db`, or `q` changes (or the
+/// JACK sample rate changes), then reused sample-to-sample.
+struct EqBand {
+	kind: EqBandKind,
+	freq: f32,
+	gain_db: f32,
+	q: f32,
+	dirty: bool,
+	sample_rate: f32,
+	// Normalized coefficients (a0 folded in).
+	b0: f32,
+	b1: f32,
+	b2: f32,
+	a1: f32,
+	a2: f32,
+	// Direct Form I state.
+	x1: f32,
+	x2: f32,
+	y1: f32,
+	y2: f32,
+}
+
+impl EqBand {
+	fn new(kind: EqBandKind, freq: f32, gain_db: f32, q: f32) -> Self {
+		Self {
+			kind,
+			freq,
+			gain_db,
+			q,
+			dirty: true,
+			sample_rate: 0.0,
+			b0: 1.0,
+			b1: 0.0,
+			b2: 0.0,
+			a1: 0.0,
+			a2: 0.0,
+			x1: 0.0,
+			x2: 0.0,
+			y1: 0.0,
+			y2: 0.0,
+		}
+	}
+
+	/// Highest `freq` that keeps `w0 = 2*pi*f/fs` below Nyquist for the given
+	/// sample rate, so the RBJ coefficients below don't alias. Before the
+	/// first JACK callback, sample_rate is unknown (0.0), so fall back to
+	/// the control's old default ceiling.
+	fn max_safe_freq(sample_rate: f32) -> f32 {
+		let nyquist = if sample_rate > 0.0 { sample_rate / 2.0 } else { 20000.0 };
+		(nyquist - 1.0).max(20.0).min(20000.0)
+	}
+
+	/// Recomputes the biquad coefficients from (freq, gain_db, q, sample_rate)
+	/// using the RBJ audio EQ cookbook, but only when something actually
+	/// changed since the last call.
+	fn recompute_coeffs(&mut self, sample_rate: f32) {
+		if !self.dirty && self.sample_rate == sample_rate {
+			return;
+		}
+		self.sample_rate = sample_rate;
+		self.dirty = false;
+		if sample_rate <= 0.0 {
+			return;
+		}
+		self.freq = self.freq.min(Self::max_safe_freq(sample_rate));
+		let a = 10f32.powf(self.gain_db / 40.0);
+		let w0 = 2.0 * std::f32::consts::PI * self.freq / sample_rate;
+		let cos_w0 = w0.cos();
+		let sin_w0 = w0.sin();
+		let alpha = sin_w0 / (2.0 * self.q);
+		let (b0, b1, b2, a0, a1, a2) = match self.kind {
+			EqBandKind::Peak => (
+				1.0 + alpha * a,
+				-2.0 * cos_w0,
+				1.0 - alpha * a,
+				1.0 + alpha / a,
+				-2.0 * cos_w0,
+				1.0 - alpha / a,
+			),
+			EqBandKind::LowShelf => {
+				let sqrt_a_alpha2 = 2.0 * a.sqrt() * alpha;
+				(
+					a * ((a + 1.0) - (a - 1.0) * cos_w0 + sqrt_a_alpha2),
+					2.0 * a * ((a - 1.0) - (a + 1.0) * cos_w0),
+					a * ((a + 1.0) - (a - 1.0) * cos_w0 - sqrt_a_alpha2),
+					(a + 1.0) + (a - 1.0) * cos_w0 + sqrt_a_alpha2,
+					-2.0 * ((a - 1.0) + (a + 1.0) * cos_w0),
+					(a + 1.0) + (a - 1.0) * cos_w0 - sqrt_a_alpha2,
+				)
+			}
+			EqBandKind::HighShelf => {
+				let sqrt_a_alpha2 = 2.0 * a.sqrt() * alpha;
+				(
+					a * ((a + 1.0) + (a - 1.0) * cos_w0 + sqrt_a_alpha2),
+					-2.0 * a * ((a - 1.0) + (a + 1.0) * cos_w0),
+					a * ((a + 1.0) + (a - 1.0) * cos_w0 - sqrt_a_alpha2),
+					(a + 1.0) - (a - 1.0) * cos_w0 + sqrt_a_alpha2,
+					2.0 * ((a - 1.0) - (a + 1.0) * cos_w0),
+					(a + 1.0) - (a - 1.0) * cos_w0 - sqrt_a_alpha2,
+				)
+			}
+		};
+		self.b0 = b0 / a0;
+		self.b1 = b1 / a0;
+		self.b2 = b2 / a0;
+		self.a1 = a1 / a0;
+		self.a2 = a2 / a0;
+	}
+
+	fn process(&mut self, x: f32) -> f32 {
+		let y = self.b0 * x + self.b1 * self.x1 + self.b2 * self.x2
+			- self.a1 * self.y1 - self.a2 * self.y2;
+		self.x2 = self.x1;
+		self.x1 = x;
+		self.y2 = self.y1;
+		self.y1 = y;
+		y
+	}
+
+	fn ui(&mut self, ui: &mut egui::Ui) {
+		ui.horizontal(|ui| {
+			ui.label(self.kind.label());
+			let max_freq = Self::max_safe_freq(self.sample_rate);
+			self.freq = self.freq.min(max_freq);
+			if ui.add(egui::DragValue::new(&mut self.freq).suffix(" Hz").range(20.0..=max_freq)).changed() {
+				self.dirty = true;
+			}
+			if ui.add(egui::DragValue::new(&mut self.gain_db).suffix(" dB").range(-18.0..=18.0).speed(0.1)).changed() {
+				self.dirty = true;
+			}
+			if ui.add(egui::DragValue::new(&mut self.q).prefix("Q ").range(0.1..=10.0).speed(0.01)).changed() {
+				self.dirty = true;
+			}
+		});
+	}
+}
+
+/// A minimal complex number, just enough to drive [`fft_in_place`].
+#[derive(Clone, Copy, Default)]
+struct Complex32 {
+	re: f32,
+	im: f32,
+}
+
+impl Complex32 {
+	fn new(re: f32, im: f32) -> Self {
+		Self { re, im }
+	}
+}
+
+impl std::ops::Add for Complex32 {
+	type Output = Complex32;
+	fn add(self, rhs: Complex32) -> Complex32 {
+		Complex32::new(self.re + rhs.re, self.im + rhs.im)
+	}
+}
+
+impl std::ops::Sub for Complex32 {
+	type Output = Complex32;
+	fn sub(self, rhs: Complex32) -> Complex32 {
+		Complex32::new(self.re - rhs.re, self.im - rhs.im)
+	}
+}
+
+impl std::ops::Mul for Complex32 {
+	type Output = Complex32;
+	fn mul(self, rhs: Complex32) -> Complex32 {
+		Complex32::new(self.re * rhs.re - self.im * rhs.im, self.re * rhs.im + self.im * rhs.re)
+	}
+}
+
+/// In-place iterative radix-2 Cooley-Tukey FFT. `data.len()` must be a
+/// power of two.
+fn fft_in_place(data: &mut [Complex32]) {
+	let n = data.len();
+	if n <= 1 {
+		return;
+	}
+	debug_assert!(n.is_power_of_two());
+	// Bit-reversal permutation.
+	let bits = n.trailing_zeros();
+	for i in 0..n {
+		let j = i.reverse_bits() >> (usize::BITS - bits);
+		if j > i {
+			data.swap(i, j);
+		}
+	}
+	// Iterative butterflies.
+	let mut len = 2;
+	while len <= n {
+		let half = len / 2;
+		let angle_step = -2.0 * std::f32::consts::PI / len as f32;
+		for start in (0..n).step_by(len) {
+			for k in 0..half {
+				let twiddle = Complex32::new((angle_step * k as f32).cos(), (angle_step * k as f32).sin());
+				let even = data[start + k];
+				let odd = data[start + k + half] * twiddle;
+				data[start + k] = even + odd;
+				data[start + k + half] = even - odd;
+			}
+		}
+		len *= 2;
+	}
+}
+
+/// Live magnitude spectrum of the master bus. Samples are pushed from the
+/// JACK process callback into a fixed-size circular buffer; the FFT itself
+/// only runs when the analyzer panel is actually drawn.
+struct Analyzer {
+	ring: Vec<f32>,
+	write_pos: usize,
+	magnitudes_db: Vec<f32>,
+	sample_rate: f32,
+}
+
+impl Analyzer {
+	fn new() -> Self {
+		Self {
+			ring: vec![0.0; ANALYZER_FFT_SIZE],
+			write_pos: 0,
+			magnitudes_db: vec![-100.0; ANALYZER_FFT_SIZE / 2],
+			sample_rate: 48000.0,
+		}
+	}
+
+	fn push_sample(&mut self, sample: f32) {
+		self.ring[self.write_pos] = sample;
+		self.write_pos = (self.write_pos + 1) % ANALYZER_FFT_SIZE;
+	}
+
+	/// Runs a Hann-windowed real FFT over the latest `ANALYZER_FFT_SIZE`
+	/// samples and smooths the resulting per-bin magnitudes in dB.
+	fn update(&mut self, sample_rate: f32) {
+		let n = ANALYZER_FFT_SIZE;
+		let mut frame: Vec<Complex32> = (0..n).map(|i| {
+			let idx = (self.write_pos + i) % n;
+			let window = 0.5 * (1.0 - (2.0 * std::f32::consts::PI * i as f32 / (n - 1) as f32).cos());
+			Complex32::new(self.ring[idx] * window, 0.0)
+		}).collect();
+		fft_in_place(&mut frame);
+		for k in 0..n / 2 {
+			let magnitude = (frame[k].re * frame[k].re + frame[k].im * frame[k].im).sqrt() / n as f32;
+			let db = 20.0 * magnitude.max(1e-9).log10();
+			if db > self.magnitudes_db[k] {
+				self.magnitudes_db[k] = db;
+			} else {
+				self.magnitudes_db[k] = self.magnitudes_db[k] * ANALYZER_DECAY + db * (1.0 - ANALYZER_DECAY);
+			}
+		}
+		self.sample_rate = sample_rate;
+	}
+
+	fn bin_freq(&self, bin: usize) -> f32 {
+		bin as f32 * self.sample_rate / ANALYZER_FFT_SIZE as f32
+	}
+
+	fn ui(&self, ui: &mut egui::Ui) {
+		let (rect, _response) = ui.allocate_exact_size(vec2(400.0, 120.0), egui::Sense::hover());
+		let painter = ui.painter();
+		painter.rect_stroke(rect, 0.0, (1.0, Color32::DARK_GRAY));
+		let min_freq = 20.0f32;
+		let max_freq = (self.sample_rate / 2.0).max(min_freq + 1.0);
+		let log_min = min_freq.log10();
+		let log_max = max_freq.log10();
+		let mut points = Vec::with_capacity(self.magnitudes_db.len());
+		for (bin, &db) in self.magnitudes_db.iter().enumerate() {
+			let freq = self.bin_freq(bin).max(min_freq);
+			let x_frac = ((freq.log10() - log_min) / (log_max - log_min)).clamp(0.0, 1.0);
+			let y_frac = ((db + 90.0) / 90.0).clamp(0.0, 1.0);
+			let x = rect.left() + x_frac * rect.width();
+			let y = rect.bottom() - y_frac * rect.height();
+			points.push(Pos2::new(x, y));
+		}
+		painter.add(egui::Shape::line(points, (1.0, Color32::from_rgb(0, 200, 0))));
+	}
+}
+
+/// A single OSC argument value. Only the two types this mixer's addresses
+/// need (`i` and `f`) are supported.
+#[derive(Clone, Copy, Debug)]
+enum OscArg {
+	Int(i32),
+	Float(f32),
+}
+
+fn osc_encode_string(s: &str, buf: &mut Vec<u8>) {
+	buf.extend_from_slice(s.as_bytes());
+	buf.push(0);
+	while buf.len() % 4 != 0 {
+		buf.push(0);
+	}
+}
+
+fn osc_decode_string(data: &[u8], pos: &mut usize) -> Option<String> {
+	let start = *pos;
+	let mut end = start;
+	while end < data.len() && data[end] != 0 {
+		end += 1;
+	}
+	let s = std::str::from_utf8(data.get(start..end)?).ok()?.to_owned();
+	let mut next = end + 1;
+	while next % 4 != 0 {
+		next += 1;
+	}
+	*pos = next;
+	Some(s)
+}
+
+fn osc_encode_message(address: &str, args: &[OscArg]) -> Vec<u8> {
+	let mut buf = Vec::new();
+	osc_encode_string(address, &mut buf);
+	let mut tags = String::from(",");
+	for arg in args {
+		tags.push(match arg {
+			OscArg::Int(_) => 'i',
+			OscArg::Float(_) => 'f',
+		});
+	}
+	osc_encode_string(&tags, &mut buf);
+	for arg in args {
+		match arg {
+			OscArg::Int(v) => buf.extend_from_slice(&v.to_be_bytes()),
+			OscArg::Float(v) => buf.extend_from_slice(&v.to_be_bytes()),
+		}
+	}
+	buf
+}
+
+fn osc_decode_message(data: &[u8]) -> Option<(String, Vec<OscArg>)> {
+	let mut pos = 0;
+	let address = osc_decode_string(data, &mut pos)?;
+	let tags = osc_decode_string(data, &mut pos)?;
+	if !tags.starts_with(',') {
+		return None;
+	}
+	let mut args = Vec::new();
+	for tag in tags.chars().skip(1) {
+		let bytes: [u8; 4] = data.get(pos..pos + 4)?.try_into().ok()?;
+		pos += 4;
+		args.push(match tag {
+			'i' => OscArg::Int(i32::from_be_bytes(bytes)),
+			'f' => OscArg::Float(f32::from_be_bytes(bytes)),
+			_ => return None,
+		});
+	}
+	Some((address, args))
+}
+
+/// Routes one decoded OSC message onto the matching `FslcMix`/`MixChannel`
+/// field, e.g. `/fslcmix/channel/0/gain f` or `/fslcmix/master/gain f`.
+fn apply_osc_message(mixer: &Mutex<FslcMix>, address: &str, args: &[OscArg]) {
+	let parts: Vec<&str> = address.trim_start_matches('/').split('/').collect();
+	let Ok(mut mix) = mixer.lock() else { return; };
+	match parts.as_slice() {
+		["fslcmix", "channel", index, param] => {
+			let Ok(index) = index.parse::<usize>() else { return; };
+			let Some(channel) = mix.channels.get_mut(index) else { return; };
+			match (*param, args.first()) {
+				("gain", Some(OscArg::Float(v))) => channel.gain = *v,
+				("pan", Some(OscArg::Float(v))) => channel.pan = *v,
+				("mute", Some(OscArg::Int(v))) => channel.mute = *v != 0,
+				("solo", Some(OscArg::Int(v))) => channel.solo = *v != 0,
+				("limit", Some(OscArg::Int(v))) => channel.limit = *v != 0,
+				_ => {}
+			}
+		}
+		["fslcmix", "master", param] => {
+			match (*param, args.first()) {
+				("gain", Some(OscArg::Float(v))) => mix.master.gain = *v,
+				("mute", Some(OscArg::Int(v))) => mix.master.mute = *v != 0,
+				("limit", Some(OscArg::Int(v))) => mix.master.limit = *v != 0,
+				_ => {}
+			}
+		}
+		["fslcmix", "normalize"] => {
+			if let Some(OscArg::Int(v)) = args.first() {
+				mix.normalize = *v != 0;
+			}
+		}
+		_ => {}
+	}
+}
+
+/// Snapshot of every OSC-addressable parameter, used to compute the
+/// minimal set of feedback messages to send after a local change.
+#[derive(Clone, PartialEq)]
+struct OscSnapshot {
+	channel_gain: Vec<f32>,
+	channel_pan: Vec<f32>,
+	channel_mute: Vec<bool>,
+	channel_solo: Vec<bool>,
+	channel_limit: Vec<bool>,
+	master_gain: f32,
+	master_mute: bool,
+	master_limit: bool,
+	normalize: bool,
+}
+
+impl OscSnapshot {
+	fn capture(channels: &[MixChannel], master: &MixChannel, normalize: bool) -> Self {
+		Self {
+			channel_gain: channels.iter().map(|c| c.gain).collect(),
+			channel_pan: channels.iter().map(|c| c.pan).collect(),
+			channel_mute: channels.iter().map(|c| c.mute).collect(),
+			channel_solo: channels.iter().map(|c| c.solo).collect(),
+			channel_limit: channels.iter().map(|c| c.limit).collect(),
+			master_gain: master.gain,
+			master_mute: master.mute,
+			master_limit: master.limit,
+			normalize,
+		}
+	}
+}
+
+/// Holds the OSC feedback UDP socket, the set of clients that have sent us
+/// a message so far, and the last state we broadcast to them.
+struct OscFeedback {
+	socket: UdpSocket,
+	clients: Arc<Mutex<Vec<std::net::SocketAddr>>>,
+	last_state: Option<OscSnapshot>,
+}
+
+impl OscFeedback {
+	/// Diffs the current mixer state against `last_state` and sends out
+	/// only the parameters that changed, or every parameter if the channel
+	/// count changed since the last call.
+	fn sync(&mut self, channels: &[MixChannel], master: &MixChannel, normalize: bool) {
+		let current = OscSnapshot::capture(channels, master, normalize);
+		let clients = match self.clients.lock() {
+			Ok(clients) => clients.clone(),
+			Err(_) => return,
+		};
+		if clients.is_empty() {
+			self.last_state = Some(current);
+			return;
+		}
+		let full_resync = match &self.last_state {
+			Some(prev) => prev.channel_gain.len() != current.channel_gain.len(),
+			None => true,
+		};
+		let mut messages: Vec<(String, OscArg)> = Vec::new();
+		for i in 0..current.channel_gain.len() {
+			let prev_gain = self.last_state.as_ref().map(|p| p.channel_gain[i]);
+			if full_resync || prev_gain != Some(current.channel_gain[i]) {
+				messages.push((format!("/fslcmix/channel/{i}/gain"), OscArg::Float(current.channel_gain[i])));
+			}
+			let prev_pan = self.last_state.as_ref().map(|p| p.channel_pan[i]);
+			if full_resync || prev_pan != Some(current.channel_pan[i]) {
+				messages.push((format!("/fslcmix/channel/{i}/pan"), OscArg::Float(current.channel_pan[i])));
+			}
+			let prev_mute = self.last_state.as_ref().map(|p| p.channel_mute[i]);
+			if full_resync || prev_mute != Some(current.channel_mute[i]) {
+				messages.push((format!("/fslcmix/channel/{i}/mute"), OscArg::Int(current.channel_mute[i] as i32)));
+			}
+			let prev_solo = self.last_state.as_ref().map(|p| p.channel_solo[i]);
+			if full_resync || prev_solo != Some(current.channel_solo[i]) {
+				messages.push((format!("/fslcmix/channel/{i}/solo"), OscArg::Int(current.channel_solo[i] as i32)));
+			}
+			let prev_limit = self.last_state.as_ref().map(|p| p.channel_limit[i]);
+			if full_resync || prev_limit != Some(current.channel_limit[i]) {
+				messages.push((format!("/fslcmix/channel/{i}/limit"), OscArg::Int(current.channel_limit[i] as i32)));
+			}
+		}
+		let prev = self.last_state.as_ref();
+		if full_resync || prev.map(|p| p.master_gain) != Some(current.master_gain) {
+			messages.push(("/fslcmix/master/gain".to_owned(), OscArg::Float(current.master_gain)));
+		}
+		if full_resync || prev.map(|p| p.master_mute) != Some(current.master_mute) {
+			messages.push(("/fslcmix/master/mute".to_owned(), OscArg::Int(current.master_mute as i32)));
+		}
+		if full_resync || prev.map(|p| p.master_limit) != Some(current.master_limit) {
+			messages.push(("/fslcmix/master/limit".to_owned(), OscArg::Int(current.master_limit as i32)));
+		}
+		if full_resync || prev.map(|p| p.normalize) != Some(current.normalize) {
+			messages.push(("/fslcmix/normalize".to_owned(), OscArg::Int(current.normalize as i32)));
+		}
+		for (address, arg) in messages {
+			let packet = osc_encode_message(&address, &[arg]);
+			for client in &clients {
+				let _ = self.socket.send_to(&packet, client);
+			}
+		}
+		self.last_state = Some(current);
+	}
+}
+
+/// Binds a UDP socket on `port`, spawns a thread that decodes incoming OSC
+/// messages and applies them to `mixer`, and returns the feedback half
+/// (a cloned socket plus the set of clients discovered so far) for sending
+/// state changes back out.
+fn start_osc_server(port: u16, mixer: Arc<Mutex<FslcMix>>) -> std::io::Result<OscFeedback> {
+	let recv_socket = UdpSocket::bind(("0.0.0.0", port))?;
+	let send_socket = recv_socket.try_clone()?;
+	let clients: Arc<Mutex<Vec<std::net::SocketAddr>>> = Arc::new(Mutex::new(Vec::new()));
+	{
+		let clients = Arc::clone(&clients);
+		std::thread::spawn(move || {
+			let mut buf = [0u8; 1024];
+			loop {
+				match recv_socket.recv_from(&mut buf) {
+					Ok((len, src)) => {
+						if let Ok(mut registered) = clients.lock() {
+							if !registered.contains(&src) {
+								registered.push(src);
+							}
+						}
+						if let Some((address, osc_args)) = osc_decode_message(&buf[..len]) {
+							apply_osc_message(&mixer, &address, &osc_args);
+						}
+					}
+					Err(err) => {
+						eprintln!("OSC recv error: {err}");
+					}
+				}
+			}
+		});
+	}
+	Ok(OscFeedback {
+		socket: send_socket,
+		clients,
+		last_state: None,
+	})
+}